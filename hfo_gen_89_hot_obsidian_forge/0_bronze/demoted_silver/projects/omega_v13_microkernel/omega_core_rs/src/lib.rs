@@ -1,7 +1,16 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::f64::consts::PI;
+use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 
+/// Bump whenever `FsmSnapshot`'s shape changes in a way that would break an
+/// older build reading a newer profile. `from_json`/`restore_state` reject
+/// any snapshot newer than this instead of silently misbehaving.
+const CURRENT_SCHEMA_VERSION: u16 = 1;
+
 #[wasm_bindgen]
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FsmStateType {
     Idle,
     IdleCoast,
@@ -11,6 +20,170 @@ pub enum FsmStateType {
     CommitCoast,
 }
 
+/// One Euro Filter (Casiez et al.) over a 2D pointer signal, tuned to cut
+/// jitter when the hand is still without adding lag during fast motion.
+struct PointerFilter {
+    min_cutoff: f64,
+    beta: f64,
+    dcutoff: f64,
+
+    x_hat: f64,
+    y_hat: f64,
+    dx_hat: f64,
+    dy_hat: f64,
+    last_ms: f64,
+    initialized: bool,
+}
+
+fn smoothing_factor(dt: f64, cutoff: f64) -> f64 {
+    let tau = 1.0 / (2.0 * PI * cutoff);
+    1.0 / (1.0 + tau / dt)
+}
+
+/// `CommitPointer` and its coast fallback `CommitCoast` both represent an
+/// in-progress commit/press for event-emission purposes.
+fn is_commit_group(state: FsmStateType) -> bool {
+    matches!(state, FsmStateType::CommitPointer | FsmStateType::CommitCoast)
+}
+
+impl PointerFilter {
+    fn new() -> Self {
+        Self {
+            min_cutoff: 1.0,
+            beta: 0.0,
+            dcutoff: 1.0,
+            x_hat: 0.0,
+            y_hat: 0.0,
+            dx_hat: 0.0,
+            dy_hat: 0.0,
+            last_ms: f64::NAN,
+            initialized: false,
+        }
+    }
+
+    fn configure(&mut self, min_cutoff: f64, beta: f64, dcutoff: f64) {
+        self.min_cutoff = min_cutoff;
+        self.beta = beta;
+        self.dcutoff = dcutoff;
+    }
+
+    fn filter(&mut self, x: f64, y: f64, now_ms: f64) {
+        if !self.initialized || self.last_ms.is_nan() {
+            self.x_hat = x;
+            self.y_hat = y;
+            self.dx_hat = 0.0;
+            self.dy_hat = 0.0;
+            self.last_ms = now_ms;
+            self.initialized = true;
+            return;
+        }
+
+        let dt = (now_ms - self.last_ms) / 1000.0;
+        if dt <= 0.0 {
+            return;
+        }
+        self.last_ms = now_ms;
+
+        let a_d = smoothing_factor(dt, self.dcutoff);
+
+        let dx = (x - self.x_hat) * (1.0 / dt);
+        self.dx_hat = a_d * dx + (1.0 - a_d) * self.dx_hat;
+        let fc_x = self.min_cutoff + self.beta * self.dx_hat.abs();
+        let a_x = smoothing_factor(dt, fc_x);
+        self.x_hat = a_x * x + (1.0 - a_x) * self.x_hat;
+
+        let dy = (y - self.y_hat) * (1.0 / dt);
+        self.dy_hat = a_d * dy + (1.0 - a_d) * self.dy_hat;
+        let fc_y = self.min_cutoff + self.beta * self.dy_hat.abs();
+        let a_y = smoothing_factor(dt, fc_y);
+        self.y_hat = a_y * y + (1.0 - a_y) * self.y_hat;
+    }
+}
+
+/// A discrete pointer/input event, drained from [`GestureFsmRs::poll_events`]
+/// so callers can dispatch synthetic input instead of re-deriving it from
+/// state diffs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+enum PointerEventKind {
+    PointerDown,
+    PointerUp,
+    PointerMove,
+    Cancel,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct PointerEvent {
+    kind: PointerEventKind,
+    x: f64,
+    y: f64,
+    confidence: f64,
+    timestamp_ms: f64,
+}
+
+/// A `PointerEvent` tagged with the hand it came from, as emitted by
+/// [`GestureFsmManagerRs::poll_events`] when tracking more than one hand.
+#[derive(Clone, Debug, Serialize)]
+struct TaggedPointerEvent {
+    hand_id: String,
+    #[serde(flatten)]
+    event: PointerEvent,
+}
+
+/// A configured gesture → next-state edge, keyed by `(from_state, gesture)`
+/// so integrators can remap the dwell-advance path (e.g. pinch to commit,
+/// point to ready) without recompiling.
+#[derive(Clone, Copy, Debug)]
+struct TransitionRule {
+    to: FsmStateType,
+    dwell_ms: f64,
+}
+
+/// One flattened `(from, gesture) -> (to, dwell_ms)` edge, used purely as
+/// the serializable form of the `transitions` map (tuple keys don't survive
+/// a JSON round-trip).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct TransitionEntry {
+    from: FsmStateType,
+    gesture: String,
+    to: FsmStateType,
+    dwell_ms: f64,
+}
+
+/// One `state -> hold gesture` edge, the serializable form of
+/// `hold_gestures` (enum keys don't survive a JSON round-trip either).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct HoldGestureEntry {
+    state: FsmStateType,
+    gesture: String,
+}
+
+/// Versioned, serde-based snapshot of a `GestureFsmRs`: its tuned
+/// thresholds, dwell/coast limits, gesture grammar, and in-flight
+/// accumulators. Produced by `to_json`/consumed by `from_json` and
+/// `restore_state` so a profile can be calibrated on one device/session
+/// and shipped to another.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct FsmSnapshot {
+    schema_version: u16,
+    state: FsmStateType,
+    conf_high: f64,
+    conf_low: f64,
+    dwell_limit_ready_ms: f64,
+    dwell_limit_commit_ms: f64,
+    coast_timeout_ms: f64,
+    current_confidence: f64,
+    #[serde(default)]
+    confidence_smoothing_alpha: f64,
+    #[serde(default)]
+    smoothed_confidence: f64,
+    dwell_accumulator_ms: f64,
+    coast_elapsed_ms: f64,
+    last_frame_ms: f64,
+    transitions: Vec<TransitionEntry>,
+    #[serde(default)]
+    hold_gestures: Vec<HoldGestureEntry>,
+}
+
 #[wasm_bindgen]
 pub struct GestureFsmRs {
     state: FsmStateType,
@@ -19,29 +192,68 @@ pub struct GestureFsmRs {
     dwell_limit_ready_ms: f64,
     dwell_limit_commit_ms: f64,
     coast_timeout_ms: f64,
-    
+
     current_confidence: f64,
+    confidence_smoothing_alpha: f64,
+    smoothed_confidence: f64,
     dwell_accumulator_ms: f64,
     coast_elapsed_ms: f64,
     last_frame_ms: f64,
+
+    pointer_filter: PointerFilter,
+    pending_events: Vec<PointerEvent>,
+    transitions: HashMap<(FsmStateType, String), TransitionRule>,
+    /// The gestures that sustain (or return to) a given state once reached,
+    /// e.g. `Ready -> {"open_palm"}`. A state can have more than one holding
+    /// gesture if multiple incoming transitions target it (e.g. both
+    /// `open_palm` and a remapped `point` arming `Ready`); `set_transition`
+    /// adds each rule's gesture to its destination's set, so adding an extra
+    /// arming gesture doesn't bump the others out of holding their
+    /// destination state. Entries are only ever added, never removed when a
+    /// rule is overwritten, and only the `Ready` set is consulted (to hold
+    /// `Ready` and to release `CommitPointer` back to it).
+    hold_gestures: HashMap<FsmStateType, HashSet<String>>,
 }
 
 #[wasm_bindgen]
 impl GestureFsmRs {
     #[wasm_bindgen(constructor)]
     pub fn new() -> Self {
+        let dwell_limit_ready_ms = 100.0;
+        let dwell_limit_commit_ms = 100.0;
+
+        let mut transitions = HashMap::new();
+        transitions.insert(
+            (FsmStateType::Idle, "open_palm".to_string()),
+            TransitionRule { to: FsmStateType::Ready, dwell_ms: dwell_limit_ready_ms },
+        );
+        transitions.insert(
+            (FsmStateType::Ready, "closed_fist".to_string()),
+            TransitionRule { to: FsmStateType::CommitPointer, dwell_ms: dwell_limit_commit_ms },
+        );
+
+        let mut hold_gestures = HashMap::new();
+        hold_gestures.insert(FsmStateType::Ready, HashSet::from(["open_palm".to_string()]));
+
         Self {
             state: FsmStateType::Idle,
             conf_high: 0.64,
             conf_low: 0.50,
-            dwell_limit_ready_ms: 100.0,
-            dwell_limit_commit_ms: 100.0,
+            dwell_limit_ready_ms,
+            dwell_limit_commit_ms,
             coast_timeout_ms: 500.0,
-            
+
             current_confidence: 0.0,
+            confidence_smoothing_alpha: 0.0,
+            smoothed_confidence: 0.0,
             dwell_accumulator_ms: 0.0,
             coast_elapsed_ms: 0.0,
             last_frame_ms: f64::NAN,
+
+            pointer_filter: PointerFilter::new(),
+            pending_events: Vec::new(),
+            transitions,
+            hold_gestures,
         }
     }
 
@@ -49,12 +261,186 @@ impl GestureFsmRs {
         self.state
     }
 
+    pub fn get_x(&self) -> f64 {
+        self.pointer_filter.x_hat
+    }
+
+    pub fn get_y(&self) -> f64 {
+        self.pointer_filter.y_hat
+    }
+
+    /// Instantaneous, per-frame confidence as reported by the caller.
+    pub fn get_confidence(&self) -> f64 {
+        self.current_confidence
+    }
+
+    /// Confidence after the smoothing in `set_confidence_smoothing` is
+    /// applied; this is what dwell/coast transitions are evaluated against.
+    pub fn get_smoothed_confidence(&self) -> f64 {
+        self.smoothed_confidence
+    }
+
+    /// Smooth per-frame confidence with an exponential moving average before
+    /// it's compared against `conf_high`/`conf_low`, so a single noisy frame
+    /// can't dump Ready/Commit into a coast state and reset dwell. `alpha`
+    /// is the weight kept on the previous smoothed value; `0.0` passes the
+    /// raw confidence straight through (the prior, unsmoothed behavior).
+    pub fn set_confidence_smoothing(&mut self, alpha: f64) {
+        self.confidence_smoothing_alpha = alpha;
+    }
+
     pub fn configure(&mut self, dwell_ready_ms: Option<f64>, dwell_commit_ms: Option<f64>, coast_timeout_ms: Option<f64>) {
-        if let Some(val) = dwell_ready_ms { self.dwell_limit_ready_ms = val; }
-        if let Some(val) = dwell_commit_ms { self.dwell_limit_commit_ms = val; }
+        if let Some(val) = dwell_ready_ms {
+            self.dwell_limit_ready_ms = val;
+            if let Some(rule) = self.transitions.get_mut(&(FsmStateType::Idle, "open_palm".to_string())) {
+                rule.dwell_ms = val;
+            }
+        }
+        if let Some(val) = dwell_commit_ms {
+            self.dwell_limit_commit_ms = val;
+            if let Some(rule) = self.transitions.get_mut(&(FsmStateType::Ready, "closed_fist".to_string())) {
+                rule.dwell_ms = val;
+            }
+        }
         if let Some(val) = coast_timeout_ms { self.coast_timeout_ms = val; }
     }
 
+    /// Remap or add a gesture transition (e.g. pinch to commit, point to
+    /// ready) without recompiling. Overwrites any existing rule for the
+    /// same `(from, gesture)` pair; the built-in open_palm/closed_fist
+    /// mapping is just the default entries in this same table. Only rules
+    /// from `Idle` and `Ready` are consulted; rules from other states are
+    /// stored (and serialized) but never fire, and an `Idle` rule targeting
+    /// `CommitPointer` is never taken.
+    pub fn set_transition(&mut self, from: FsmStateType, gesture: &str, to: FsmStateType, dwell_ms: f64) {
+        self.transitions.insert((from, gesture.to_string()), TransitionRule { to, dwell_ms });
+        self.hold_gestures.entry(to).or_default().insert(gesture.to_string());
+    }
+
+    fn lookup_transition(&self, from: FsmStateType, gesture: &str) -> Option<TransitionRule> {
+        self.transitions.get(&(from, gesture.to_string())).copied()
+    }
+
+    fn is_hold_gesture(&self, state: FsmStateType, gesture: &str) -> bool {
+        self.hold_gestures.get(&state).is_some_and(|gestures| gestures.contains(gesture))
+    }
+
+    /// Tune the One Euro Filter applied to incoming pointer coordinates.
+    /// `min_cutoff` sets the baseline jitter rejection, `beta` controls how
+    /// much speed reduces lag, and `dcutoff` smooths the derivative estimate.
+    pub fn configure_filter(&mut self, min_cutoff: f64, beta: f64, dcutoff: f64) {
+        self.pointer_filter.configure(min_cutoff, beta, dcutoff);
+    }
+
+    /// Drain and return pending pointer events as a JSON array, so a web page
+    /// can dispatch synthetic DOM PointerEvents without re-deriving
+    /// transitions from `get_state()` diffs.
+    pub fn poll_events(&mut self) -> String {
+        let events = std::mem::take(&mut self.pending_events);
+        serde_json::to_string(&events).unwrap_or_default()
+    }
+
+    /// Snapshot thresholds, gesture grammar, and in-flight accumulators as a
+    /// versioned JSON string, suitable for persisting a per-user profile.
+    pub fn to_json(&self) -> Result<String, JsValue> {
+        serde_json::to_string(&self.snapshot()).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Build a fresh `GestureFsmRs` from a previously saved profile. Fails if
+    /// the snapshot's `schema_version` is newer than this build supports.
+    pub fn from_json(json: &str) -> Result<GestureFsmRs, JsValue> {
+        let snapshot = Self::parse_snapshot(json)?;
+        let mut fsm = GestureFsmRs::new();
+        fsm.apply_snapshot(snapshot);
+        Ok(fsm)
+    }
+
+    /// Reconstruct this instance's state (including mid-gesture dwell and
+    /// coast accumulators) from a previously saved profile, in place.
+    pub fn restore_state(&mut self, json: &str) -> Result<(), JsValue> {
+        let snapshot = Self::parse_snapshot(json)?;
+        self.apply_snapshot(snapshot);
+        Ok(())
+    }
+
+    fn parse_snapshot(json: &str) -> Result<FsmSnapshot, JsValue> {
+        let snapshot: FsmSnapshot =
+            serde_json::from_str(json).map_err(|e| JsValue::from_str(&e.to_string()))?;
+        if snapshot.schema_version > CURRENT_SCHEMA_VERSION {
+            return Err(JsValue::from_str(&format!(
+                "FSM profile schema_version {} is newer than this build supports ({})",
+                snapshot.schema_version, CURRENT_SCHEMA_VERSION
+            )));
+        }
+        Ok(snapshot)
+    }
+
+    fn snapshot(&self) -> FsmSnapshot {
+        let transitions = self
+            .transitions
+            .iter()
+            .map(|((from, gesture), rule)| TransitionEntry {
+                from: *from,
+                gesture: gesture.clone(),
+                to: rule.to,
+                dwell_ms: rule.dwell_ms,
+            })
+            .collect();
+
+        let hold_gestures = self
+            .hold_gestures
+            .iter()
+            .flat_map(|(state, gestures)| {
+                gestures.iter().map(move |gesture| HoldGestureEntry { state: *state, gesture: gesture.clone() })
+            })
+            .collect();
+
+        FsmSnapshot {
+            schema_version: CURRENT_SCHEMA_VERSION,
+            state: self.state,
+            conf_high: self.conf_high,
+            conf_low: self.conf_low,
+            dwell_limit_ready_ms: self.dwell_limit_ready_ms,
+            dwell_limit_commit_ms: self.dwell_limit_commit_ms,
+            coast_timeout_ms: self.coast_timeout_ms,
+            current_confidence: self.current_confidence,
+            confidence_smoothing_alpha: self.confidence_smoothing_alpha,
+            smoothed_confidence: self.smoothed_confidence,
+            dwell_accumulator_ms: self.dwell_accumulator_ms,
+            coast_elapsed_ms: self.coast_elapsed_ms,
+            last_frame_ms: self.last_frame_ms,
+            transitions,
+            hold_gestures,
+        }
+    }
+
+    fn apply_snapshot(&mut self, snapshot: FsmSnapshot) {
+        self.state = snapshot.state;
+        self.conf_high = snapshot.conf_high;
+        self.conf_low = snapshot.conf_low;
+        self.dwell_limit_ready_ms = snapshot.dwell_limit_ready_ms;
+        self.dwell_limit_commit_ms = snapshot.dwell_limit_commit_ms;
+        self.coast_timeout_ms = snapshot.coast_timeout_ms;
+        self.current_confidence = snapshot.current_confidence;
+        self.confidence_smoothing_alpha = snapshot.confidence_smoothing_alpha;
+        self.smoothed_confidence = snapshot.smoothed_confidence;
+        self.dwell_accumulator_ms = snapshot.dwell_accumulator_ms;
+        self.coast_elapsed_ms = snapshot.coast_elapsed_ms;
+        self.last_frame_ms = snapshot.last_frame_ms;
+        self.transitions = snapshot
+            .transitions
+            .into_iter()
+            .map(|entry| ((entry.from, entry.gesture), TransitionRule { to: entry.to, dwell_ms: entry.dwell_ms }))
+            .collect();
+        if !snapshot.hold_gestures.is_empty() {
+            let mut hold_gestures: HashMap<FsmStateType, HashSet<String>> = HashMap::new();
+            for entry in snapshot.hold_gestures {
+                hold_gestures.entry(entry.state).or_default().insert(entry.gesture);
+            }
+            self.hold_gestures = hold_gestures;
+        }
+    }
+
     pub fn force_coast(&mut self) {
         match self.state {
             FsmStateType::Idle => self.state = FsmStateType::IdleCoast,
@@ -64,16 +450,25 @@ impl GestureFsmRs {
         }
     }
 
-    pub fn process_frame(&mut self, gesture: &str, confidence: f64, _x: f64, _y: f64, now_ms: f64) {
+    pub fn process_frame(&mut self, gesture: &str, confidence: f64, x: f64, y: f64, now_ms: f64) {
         let delta_ms = if self.last_frame_ms.is_nan() { 0.0 } else { now_ms - self.last_frame_ms };
         self.last_frame_ms = now_ms;
         self.current_confidence = confidence;
+        self.smoothed_confidence = if self.confidence_smoothing_alpha <= 0.0 {
+            confidence
+        } else {
+            (1.0 - self.confidence_smoothing_alpha) * confidence
+                + self.confidence_smoothing_alpha * self.smoothed_confidence
+        };
+        self.pointer_filter.filter(x, y, now_ms);
+        let prev_state = self.state;
 
         if self.is_coast_state() {
             self.coast_elapsed_ms += delta_ms;
             if self.coast_elapsed_ms >= self.coast_timeout_ms {
                 self.transition_to(FsmStateType::Idle);
                 self.dwell_accumulator_ms = 0.0;
+                self.push_event(PointerEventKind::Cancel, now_ms);
                 return;
             }
         } else {
@@ -88,6 +483,33 @@ impl GestureFsmRs {
             FsmStateType::CommitPointer => self.handle_commit(gesture, delta_ms),
             FsmStateType::CommitCoast => self.handle_commit_coast(gesture),
         }
+
+        self.emit_transition_events(prev_state, now_ms);
+    }
+
+    fn push_event(&mut self, kind: PointerEventKind, timestamp_ms: f64) {
+        self.pending_events.push(PointerEvent {
+            kind,
+            x: self.get_x(),
+            y: self.get_y(),
+            confidence: self.smoothed_confidence,
+            timestamp_ms,
+        });
+    }
+
+    fn emit_transition_events(&mut self, prev_state: FsmStateType, now_ms: f64) {
+        // CommitCoast is a brief confidence dip mid-commit, not a release: treat it as
+        // still "down" so a Commit -> CommitCoast -> Commit round trip doesn't re-fire
+        // PointerDown without an intervening PointerUp.
+        if !is_commit_group(prev_state) && self.state == FsmStateType::CommitPointer {
+            self.push_event(PointerEventKind::PointerDown, now_ms);
+        }
+        if is_commit_group(prev_state) && matches!(self.state, FsmStateType::Ready | FsmStateType::Idle) {
+            self.push_event(PointerEventKind::PointerUp, now_ms);
+        }
+        if matches!(self.state, FsmStateType::Ready | FsmStateType::CommitPointer) {
+            self.push_event(PointerEventKind::PointerMove, now_ms);
+        }
     }
 
     fn is_coast_state(&self) -> bool {
@@ -102,37 +524,45 @@ impl GestureFsmRs {
     }
 
     fn handle_idle(&mut self, gesture: &str, delta_ms: f64) {
-        if gesture == "open_palm" && self.current_confidence >= self.conf_high {
-            self.dwell_accumulator_ms += delta_ms;
-            if self.dwell_accumulator_ms >= self.dwell_limit_ready_ms {
-                self.transition_to(FsmStateType::Ready);
-                self.dwell_accumulator_ms = 0.0;
-            }
-        } else {
-            self.dwell_accumulator_ms = (self.dwell_accumulator_ms - (delta_ms * 2.0)).max(0.0);
-            if self.current_confidence < self.conf_low {
-                self.transition_to(FsmStateType::IdleCoast);
+        if let Some(rule) = self.lookup_transition(FsmStateType::Idle, gesture) {
+            if self.smoothed_confidence >= self.conf_high {
+                self.dwell_accumulator_ms += delta_ms;
+                if self.dwell_accumulator_ms >= rule.dwell_ms {
+                    self.transition_to(rule.to);
+                    self.dwell_accumulator_ms = 0.0;
+                }
+                return;
             }
         }
+        self.dwell_accumulator_ms = (self.dwell_accumulator_ms - (delta_ms * 2.0)).max(0.0);
+        if self.smoothed_confidence < self.conf_low {
+            self.transition_to(FsmStateType::IdleCoast);
+        }
     }
 
     fn handle_idle_coast(&mut self, _gesture: &str) {
-        if self.current_confidence >= self.conf_low {
+        if self.smoothed_confidence >= self.conf_low {
             self.transition_to(FsmStateType::Idle);
         }
     }
 
     fn handle_ready(&mut self, gesture: &str, delta_ms: f64) {
-        if gesture == "closed_fist" && self.current_confidence >= self.conf_high {
-            self.dwell_accumulator_ms += delta_ms;
-            if self.dwell_accumulator_ms >= self.dwell_limit_commit_ms {
-                self.transition_to(FsmStateType::CommitPointer);
-                self.dwell_accumulator_ms = 0.0;
+        if let Some(rule) = self.lookup_transition(FsmStateType::Ready, gesture) {
+            if self.smoothed_confidence >= self.conf_high {
+                self.dwell_accumulator_ms += delta_ms;
+                if self.dwell_accumulator_ms >= rule.dwell_ms {
+                    self.transition_to(rule.to);
+                    self.dwell_accumulator_ms = 0.0;
+                }
+                return;
             }
-        } else if gesture != "open_palm" && gesture != "closed_fist" {
+        } else if !self.is_hold_gesture(FsmStateType::Ready, gesture) {
             self.transition_to(FsmStateType::Idle);
             self.dwell_accumulator_ms = 0.0;
-        } else if self.current_confidence < self.conf_low {
+            return;
+        }
+
+        if self.smoothed_confidence < self.conf_low {
             self.transition_to(FsmStateType::ReadyCoast);
         } else {
             self.dwell_accumulator_ms = (self.dwell_accumulator_ms - (delta_ms * 2.0)).max(0.0);
@@ -140,23 +570,276 @@ impl GestureFsmRs {
     }
 
     fn handle_ready_coast(&mut self, _gesture: &str) {
-        if self.current_confidence >= self.conf_low {
+        if self.smoothed_confidence >= self.conf_low {
             self.transition_to(FsmStateType::Ready);
         }
     }
 
     fn handle_commit(&mut self, gesture: &str, _delta_ms: f64) {
-        if gesture == "open_palm" && self.current_confidence >= self.conf_high {
+        if self.is_hold_gesture(FsmStateType::Ready, gesture) && self.smoothed_confidence >= self.conf_high {
             self.transition_to(FsmStateType::Ready);
             self.dwell_accumulator_ms = 0.0;
-        } else if self.current_confidence < self.conf_low {
+        } else if self.smoothed_confidence < self.conf_low {
             self.transition_to(FsmStateType::CommitCoast);
         }
     }
 
     fn handle_commit_coast(&mut self, _gesture: &str) {
-        if self.current_confidence >= self.conf_low {
+        if self.smoothed_confidence >= self.conf_low {
             self.transition_to(FsmStateType::CommitPointer);
         }
     }
 }
+
+/// Owns one `GestureFsmRs` per tracked hand, keyed by a stable hand id, so
+/// an app tracking two hands doesn't have to juggle separate FSM instances
+/// or hand-roll cross-hand bookkeeping (e.g. one hand arming Ready while the
+/// other commits).
+#[wasm_bindgen]
+pub struct GestureFsmManagerRs {
+    hands: HashMap<String, GestureFsmRs>,
+    pending_events: Vec<TaggedPointerEvent>,
+}
+
+impl Default for GestureFsmManagerRs {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[wasm_bindgen]
+impl GestureFsmManagerRs {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self { hands: HashMap::new(), pending_events: Vec::new() }
+    }
+
+    /// Route a frame to the FSM for `hand_id`, lazily creating one with
+    /// default thresholds if this is the first frame seen for that hand.
+    pub fn process_frame(&mut self, hand_id: &str, gesture: &str, confidence: f64, x: f64, y: f64, now_ms: f64) {
+        let fsm = self.hands.entry(hand_id.to_string()).or_insert_with(GestureFsmRs::new);
+        fsm.process_frame(gesture, confidence, x, y, now_ms);
+        let events = drain_tagged_events(hand_id, fsm);
+        self.pending_events.extend(events);
+    }
+
+    pub fn get_state(&self, hand_id: &str) -> Option<FsmStateType> {
+        self.hands.get(hand_id).map(|fsm| fsm.get_state())
+    }
+
+    /// Drop hands that haven't reported a frame within `timeout_ms`. Each
+    /// stale FSM is first forced into its coast state and then driven past
+    /// its own coast timeout, so a pending commit still emits a closing
+    /// `Cancel` event (the same event the coast timeout always produces,
+    /// not `PointerUp`) before the FSM disappears.
+    pub fn prune(&mut self, now_ms: f64, timeout_ms: f64) {
+        let stale_hand_ids: Vec<String> = self
+            .hands
+            .iter()
+            .filter(|(_, fsm)| now_ms - fsm.last_frame_ms >= timeout_ms)
+            .map(|(hand_id, _)| hand_id.clone())
+            .collect();
+
+        for hand_id in stale_hand_ids {
+            if let Some(fsm) = self.hands.get_mut(&hand_id) {
+                fsm.force_coast();
+                let elapsed_ms = fsm.last_frame_ms + fsm.coast_timeout_ms;
+                fsm.process_frame("", fsm.current_confidence, fsm.get_x(), fsm.get_y(), elapsed_ms);
+                let events = drain_tagged_events(&hand_id, fsm);
+                self.pending_events.extend(events);
+            }
+            self.hands.remove(&hand_id);
+        }
+    }
+
+    /// Drain and return pending events for every tracked hand as a single
+    /// JSON array, each entry tagged with its `hand_id`.
+    pub fn poll_events(&mut self) -> String {
+        let events = std::mem::take(&mut self.pending_events);
+        serde_json::to_string(&events).unwrap_or_default()
+    }
+}
+
+fn drain_tagged_events(hand_id: &str, fsm: &mut GestureFsmRs) -> Vec<TaggedPointerEvent> {
+    std::mem::take(&mut fsm.pending_events)
+        .into_iter()
+        .map(|event| TaggedPointerEvent { hand_id: hand_id.to_string(), event })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed `steps` frames of a steady `gesture`/`confidence` pair 20ms apart,
+    /// advancing `now` as it goes.
+    fn drive(fsm: &mut GestureFsmRs, gesture: &str, confidence: f64, now: &mut f64, steps: usize) {
+        for _ in 0..steps {
+            fsm.process_frame(gesture, confidence, 0.0, 0.0, *now);
+            *now += 20.0;
+        }
+    }
+
+    #[test]
+    fn one_euro_filter_converges_to_steady_input() {
+        let mut fsm = GestureFsmRs::new();
+        fsm.process_frame("", 0.0, 0.0, 0.0, 0.0);
+
+        let mut now = 16.0;
+        for _ in 0..80 {
+            fsm.process_frame("", 0.0, 10.0, 20.0, now);
+            now += 16.0;
+        }
+
+        assert!((fsm.get_x() - 10.0).abs() < 0.2, "x did not converge: {}", fsm.get_x());
+        assert!((fsm.get_y() - 20.0).abs() < 0.2, "y did not converge: {}", fsm.get_y());
+    }
+
+    #[test]
+    fn one_euro_filter_damps_a_single_noisy_spike() {
+        let mut fsm = GestureFsmRs::new();
+        let mut now = 0.0;
+        for _ in 0..20 {
+            fsm.process_frame("", 0.0, 5.0, 5.0, now);
+            now += 16.0;
+        }
+
+        fsm.process_frame("", 0.0, 500.0, 500.0, now);
+
+        assert!(fsm.get_x() < 150.0, "a single outlier sample jumped the filter straight to it: {}", fsm.get_x());
+    }
+
+    #[test]
+    fn commit_dwell_emits_pointer_down_then_up_on_release() {
+        let mut fsm = GestureFsmRs::new();
+        let mut now = 0.0;
+        drive(&mut fsm, "open_palm", 0.9, &mut now, 10);
+        assert_eq!(fsm.get_state(), FsmStateType::Ready);
+
+        drive(&mut fsm, "closed_fist", 0.9, &mut now, 10);
+        assert_eq!(fsm.get_state(), FsmStateType::CommitPointer);
+
+        let events: Vec<serde_json::Value> = serde_json::from_str(&fsm.poll_events()).unwrap();
+        assert!(events.iter().any(|e| e["kind"] == "PointerDown"));
+
+        fsm.process_frame("open_palm", 0.9, 0.0, 0.0, now);
+        assert_eq!(fsm.get_state(), FsmStateType::Ready);
+
+        let events: Vec<serde_json::Value> = serde_json::from_str(&fsm.poll_events()).unwrap();
+        assert!(events.iter().any(|e| e["kind"] == "PointerUp"));
+    }
+
+    #[test]
+    fn commit_coast_round_trip_does_not_double_fire_pointer_down() {
+        let mut fsm = GestureFsmRs::new();
+        let mut now = 0.0;
+        drive(&mut fsm, "open_palm", 0.9, &mut now, 10);
+        drive(&mut fsm, "closed_fist", 0.9, &mut now, 10);
+        assert_eq!(fsm.get_state(), FsmStateType::CommitPointer);
+        fsm.poll_events();
+
+        // A brief low-confidence dip coasts without ever releasing...
+        fsm.process_frame("closed_fist", 0.1, 0.0, 0.0, now);
+        now += 20.0;
+        assert_eq!(fsm.get_state(), FsmStateType::CommitCoast);
+
+        // ...and recovering should resume the same press, not start a new one.
+        fsm.process_frame("closed_fist", 0.9, 0.0, 0.0, now);
+        assert_eq!(fsm.get_state(), FsmStateType::CommitPointer);
+
+        let events: Vec<serde_json::Value> = serde_json::from_str(&fsm.poll_events()).unwrap();
+        let down_count = events.iter().filter(|e| e["kind"] == "PointerDown").count();
+        assert_eq!(down_count, 0, "recovering from a brief CommitCoast dip must not re-fire PointerDown");
+    }
+
+    #[test]
+    fn remapped_arm_gesture_stays_in_ready_when_held() {
+        let mut fsm = GestureFsmRs::new();
+        fsm.set_transition(FsmStateType::Idle, "point", FsmStateType::Ready, 50.0);
+
+        let mut now = 0.0;
+        drive(&mut fsm, "point", 0.9, &mut now, 10);
+        assert_eq!(fsm.get_state(), FsmStateType::Ready);
+
+        // Continuing to hold the remapped gesture must not drop back to Idle.
+        fsm.process_frame("point", 0.9, 0.0, 0.0, now);
+        assert_eq!(fsm.get_state(), FsmStateType::Ready);
+    }
+
+    #[test]
+    fn adding_a_second_incoming_gesture_does_not_evict_the_first_from_holding() {
+        let mut fsm = GestureFsmRs::new();
+        // Ready already holds on the default "open_palm"; add "point" as a
+        // second gesture that also arms Ready, without remapping the first.
+        fsm.set_transition(FsmStateType::Idle, "point", FsmStateType::Ready, 50.0);
+
+        let mut now = 0.0;
+        drive(&mut fsm, "open_palm", 0.9, &mut now, 10);
+        assert_eq!(fsm.get_state(), FsmStateType::Ready);
+
+        // Holding the original gesture must still keep Ready, even though a
+        // second gesture was registered afterward.
+        fsm.process_frame("open_palm", 0.9, 0.0, 0.0, now);
+        assert_eq!(fsm.get_state(), FsmStateType::Ready);
+    }
+
+    #[test]
+    fn snapshot_round_trip_preserves_smoothed_confidence() {
+        let mut fsm = GestureFsmRs::new();
+        fsm.set_confidence_smoothing(0.9);
+
+        let mut now = 0.0;
+        drive(&mut fsm, "open_palm", 0.9, &mut now, 30);
+        fsm.process_frame("open_palm", 0.0, 0.0, 0.0, now);
+        let smoothed_before = fsm.get_smoothed_confidence();
+        assert!(smoothed_before > 0.1, "smoothing should keep this above the raw 0.0 dip");
+
+        let json = fsm.to_json().unwrap();
+        let restored = GestureFsmRs::from_json(&json).unwrap();
+
+        assert!((restored.get_smoothed_confidence() - smoothed_before).abs() < 1e-9);
+    }
+
+    #[test]
+    fn snapshot_round_trip_preserves_hold_gesture_remap() {
+        let mut fsm = GestureFsmRs::new();
+        fsm.set_transition(FsmStateType::Idle, "point", FsmStateType::Ready, 50.0);
+
+        let mut now = 0.0;
+        drive(&mut fsm, "point", 0.9, &mut now, 10);
+        assert_eq!(fsm.get_state(), FsmStateType::Ready);
+
+        let json = fsm.to_json().unwrap();
+        let mut restored = GestureFsmRs::from_json(&json).unwrap();
+        assert_eq!(restored.get_state(), FsmStateType::Ready);
+
+        restored.process_frame("point", 0.9, 0.0, 0.0, now);
+        assert_eq!(
+            restored.get_state(),
+            FsmStateType::Ready,
+            "restored FSM should still honor the remapped hold gesture"
+        );
+    }
+
+    #[test]
+    fn prune_removes_stale_hand_and_emits_cancel_for_pending_commit() {
+        let mut manager = GestureFsmManagerRs::new();
+        let mut now = 0.0;
+        for _ in 0..10 {
+            manager.process_frame("hand-1", "open_palm", 0.9, 0.0, 0.0, now);
+            now += 20.0;
+        }
+        for _ in 0..10 {
+            manager.process_frame("hand-1", "closed_fist", 0.9, 0.0, 0.0, now);
+            now += 20.0;
+        }
+        assert_eq!(manager.get_state("hand-1"), Some(FsmStateType::CommitPointer));
+        manager.poll_events();
+
+        manager.prune(now + 10_000.0, 1000.0);
+        assert_eq!(manager.get_state("hand-1"), None);
+
+        let events: Vec<serde_json::Value> = serde_json::from_str(&manager.poll_events()).unwrap();
+        assert!(events.iter().any(|e| e["kind"] == "Cancel" && e["hand_id"] == "hand-1"));
+    }
+}